@@ -8,161 +8,329 @@
 #![no_std]
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 /******************************************************************************/
 
-/// A trait that allow generic modulator implementations
-pub trait Modulator {
-    /// The data type used for the integrator
-    type SigmaType;
+use num_traits::{Bounded, NumCast, Signed, ToPrimitive, Zero};
+
+/******************************************************************************/
+
+/// A trait that allows generic modulator implementations
+///
+/// Implement this for any numeric type — fixed-point (e.g. the `fixed` crate's `FixedI32`),
+/// a saturating wrapper, or anything else with a sensible signed accumulator and full-scale
+/// reference — to get a working [`Pdm`] for it. `SigmaType` must have enough headroom over
+/// `Self` to accumulate `N` integrator stages without overflowing.
+pub trait Modulator: NumCast + Bounded + Zero + PartialEq + Copy {
+    /// The signed accumulator type used for the integrator
+    type SigmaType: Signed + NumCast + Zero + PartialOrd + Copy;
+
+    /// Returns the full-scale reference value the quantizer compares the integrator against,
+    /// expressed in `SigmaType`
+    fn full_scale() -> Self::SigmaType;
+
+    /// Returns whether `Self`'s natural range starts at zero, as opposed to being centered on
+    /// it like signed integers or `[-1.0, 1.0]` floats — this decides whether the low-side
+    /// quantizer feedback is zero or `-full_scale()`
+    fn is_unsigned() -> bool {
+        Self::min_value() == Self::zero()
+    }
+}
+
+/******************************************************************************/
+
+/// Bit ordering used when packing a run of modulator outputs into a word or byte buffer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The first bit produced becomes the most significant bit
+    MsbFirst,
+    /// The first bit produced becomes the least significant bit
+    LsbFirst
 }
 
 /******************************************************************************/
 
 /// Pulse Density Modulator
 ///
-/// Contains the current setpoint and integrator values
-pub struct Pdm<T: Modulator> {
+/// Contains the current setpoint and integrator values. The modulator order is selected via the
+/// const generic parameter `N` (one integrator stage per order), which defaults to 2 — the
+/// classic second-order sigma-delta loop. Order 1 gives lower latency and less out-of-band noise
+/// suppression; orders 3-5 trade stability margin for steeper noise shaping. Above second order,
+/// a single shared feedback term is no longer guaranteed to keep every integrator bounded on its
+/// own, so each stage is clamped to `±4*N` full-scale steps, at every order, to keep the loop from
+/// running away. The clamp is not transparent: from order 3 up the stages after the first reach
+/// it on most steps, and at order 2 the second stage reaches it for setpoints near either end of
+/// the range. The first stage stays inside it, so the long-run average still follows the setpoint.
+pub struct Pdm<T: Modulator, const N: usize = 2> {
     value: T,
-    sigma: [T::SigmaType; 2]
+    sigma: [T::SigmaType; N]
+}
+
+impl<T: Modulator, const N: usize> Default for Pdm<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl<T: Modulator> Pdm<T> {
+impl<T: Modulator, const N: usize> Pdm<T, N> {
+    /// Initializes a new modulator
+    ///
+    /// Values are expected in `[-T::full_scale(), T::full_scale()]` for signed/float types, or
+    /// `[0, T::full_scale()]` for unsigned ones.
+    pub fn new() -> Self {
+        Self {
+            value: T::zero(),
+            sigma: [T::SigmaType::zero(); N]
+        }
+    }
+
     /// Sets the modulator value
     pub fn set_value(&mut self, value: T) {
         self.value = value
     }
+
+    /// Clamps `value` to `[-bound, bound]`
+    ///
+    /// Above second order a single shared feedback term can't keep every integrator stage
+    /// bounded on its own, so [`advance`](Self::advance) clamps each stage to a generous
+    /// multiple of full scale to keep the loop from running away.
+    fn clamp_sigma(value: T::SigmaType, bound: T::SigmaType) -> T::SigmaType {
+        if value > bound {
+            bound
+        } else if value < -bound {
+            -bound
+        } else {
+            value
+        }
+    }
+
+    /// Advances the integrator chain by one step and returns the quantizer bit, letting the
+    /// caller bias the quantizer's decision (e.g. to inject dither) without that bias being
+    /// folded into the stored integrator state
+    fn advance(&mut self, bias: T::SigmaType) -> bool {
+        let high = T::full_scale();
+        let low = if T::is_unsigned() { T::SigmaType::zero() } else { -high };
+        let bit = self.sigma[N - 1] + bias >= T::SigmaType::zero();
+        let f = if bit { high } else { low };
+        let bound = high * <T::SigmaType as NumCast>::from(4 * N).unwrap();
+
+        let value = <T::SigmaType as NumCast>::from(self.value).unwrap();
+        let mut sigma_new = [T::SigmaType::zero(); N];
+        sigma_new[0] = Self::clamp_sigma(self.sigma[0] + value - f, bound);
+        for i in 1..N {
+            sigma_new[i] = Self::clamp_sigma(self.sigma[i] + sigma_new[i - 1] - f, bound);
+        }
+        self.sigma = sigma_new;
+        bit
+    }
+
+    /// Returns the next output value of the modulator
+    pub fn update(&mut self) -> bool {
+        self.advance(T::SigmaType::zero())
+    }
+
+    /// Returns the next output value of the modulator, applying TPDF dither to the quantizer decision
+    ///
+    /// Draws two independent uniform samples in `[0, T::full_scale())` from `rng` and subtracts
+    /// them to form zero-mean triangular dither, which perturbs the quantizer's decision before
+    /// it is fed back into the integrator chain — the dither value itself is never folded into
+    /// the stored `sigma` state, so the loop's long-term average stays unbiased.
+    pub fn update_dithered<R: rand_core::RngCore>(&mut self, rng: &mut R) -> bool {
+        let scale = T::full_scale().to_f64().unwrap();
+        let u1 = rng.next_u32() as f64 / (u32::MAX as f64 + 1.0) * scale;
+        let u2 = rng.next_u32() as f64 / (u32::MAX as f64 + 1.0) * scale;
+        let dither = <T::SigmaType as NumCast>::from(u1 - u2).unwrap_or_else(T::SigmaType::zero);
+
+        self.advance(dither)
+    }
 }
 
 /******************************************************************************/
 
-macro_rules! gen_unsigned_impl {
-    ($T: ty, $S: ty) => {
+macro_rules! impl_modulator {
+    ($T: ty, $S: ty, $full_scale: expr) => {
         impl Modulator for $T {
             type SigmaType = $S;
-        }
 
-        impl Pdm<$T> {
-            /// Initializes a new modulator
-            pub fn new() -> Self {
-                Self {
-                    value: 0,
-                    sigma: [0; 2]
-                }
-            }
-
-            /// Returns the next output value of the modulator
-            pub fn update(&mut self) -> bool {
-                let mut sigma_new: [$S; 2] = [0; 2];
-                if self.sigma[1] >= 0 {
-                    sigma_new[0] = self.sigma[0] + self.value as $S - <$T>::MAX as $S;
-                    sigma_new[1] = self.sigma[1] + sigma_new[0] - <$T>::MAX as $S;
-                } else {
-                    sigma_new[0] = self.sigma[0] + self.value as $S;
-                    sigma_new[1] = self.sigma[1] + sigma_new[0];
-                }
-                self.sigma = sigma_new;
-                self.sigma[1] >= 0
+            fn full_scale() -> $S {
+                $full_scale
             }
         }
     };
 }
 
-gen_unsigned_impl!(u64, i128);
-gen_unsigned_impl!(u32, i64);
-gen_unsigned_impl!(u16, i32);
-gen_unsigned_impl!(u8, i16);
+impl_modulator!(u64, i128, u64::MAX as i128);
+impl_modulator!(u32, i64, u32::MAX as i64);
+impl_modulator!(u16, i32, u16::MAX as i32);
+impl_modulator!(u8, i16, u8::MAX as i16);
+impl_modulator!(i64, i128, i64::MAX as i128);
+impl_modulator!(i32, i64, i32::MAX as i64);
+impl_modulator!(i16, i32, i16::MAX as i32);
+impl_modulator!(i8, i16, i8::MAX as i16);
+impl_modulator!(f64, f64, 1.0);
+impl_modulator!(f32, f32, 1.0);
 
 /******************************************************************************/
 
-macro_rules! gen_signed_impl {
-    ($T: ty, $S: ty) => {
-        impl Modulator for $T {
-            type SigmaType = $S;
+macro_rules! gen_block_word {
+    ($name: ident, $with_count: ident, $W: ty, $bits: literal) => {
+        /// Runs the modulator for a whole word, packing each output bit according to `order`
+        pub fn $name(&mut self, order: BitOrder) -> $W {
+            self.$with_count(order).0
         }
 
-        impl Default for Pdm<$T> {
-            fn default() -> Self {
-                Self {
-                    value: 0,
-                    sigma: [0; 2]
-                }
-            }
-        }
-
-        impl Pdm<$T> {
-            /// Initializes a new modulator
-            pub fn new() -> Self {
-                Default::default()
-            }
-
-            /// Returns the next output value of the modulator
-            pub fn update(&mut self) -> bool {
-                let mut sigma_new: [$S; 2] = [0; 2];
-                if self.sigma[1] >= 0 {
-                    sigma_new[0] = self.sigma[0] + self.value as $S - <$T>::MAX as $S;
-                    sigma_new[1] = self.sigma[1] + sigma_new[0] - <$T>::MAX as $S;
-                } else {
-                    sigma_new[0] = self.sigma[0] + self.value as $S + <$T>::MAX as $S;
-                    sigma_new[1] = self.sigma[1] + sigma_new[0] + <$T>::MAX as $S;
+        /// Same as the non-counted variant, but also returns the number of set bits produced
+        pub fn $with_count(&mut self, order: BitOrder) -> ($W, u32) {
+            let mut word: $W = 0;
+            let mut count = 0u32;
+            for i in 0..$bits {
+                if self.update() {
+                    count += 1;
+                    let shift = match order {
+                        BitOrder::MsbFirst => $bits - 1 - i,
+                        BitOrder::LsbFirst => i
+                    };
+                    word |= 1 << shift;
                 }
-                self.sigma = sigma_new;
-                self.sigma[1] >= 0
             }
+            (word, count)
         }
     };
 }
 
-gen_signed_impl!(i64, i128);
-gen_signed_impl!(i32, i64);
-gen_signed_impl!(i16, i32);
-gen_signed_impl!(i8, i16);
+impl<T: Modulator, const N: usize> Pdm<T, N> {
+    gen_block_word!(update_block_u8, update_block_u8_with_count, u8, 8);
+    gen_block_word!(update_block_u16, update_block_u16_with_count, u16, 16);
+    gen_block_word!(update_block_u32, update_block_u32_with_count, u32, 32);
+    gen_block_word!(update_block_u64, update_block_u64_with_count, u64, 64);
+
+    /// Runs the modulator to fill `buf` with packed output bits, 8 per byte, and returns
+    /// the total number of set bits produced
+    pub fn update_block_bytes(&mut self, buf: &mut [u8], order: BitOrder) -> u32 {
+        let mut count = 0;
+        for byte in buf.iter_mut() {
+            let (b, c) = self.update_block_u8_with_count(order);
+            *byte = b;
+            count += c;
+        }
+        count
+    }
+}
 
 /******************************************************************************/
 
-macro_rules! gen_float_impl {
-    ($T: ty) => {
-        impl Modulator for $T {
-            type SigmaType = $T;
-        }
+#[cfg(feature = "simd")]
+pub use simd::{mask_to_bits, PdmSimd};
+
+#[cfg(feature = "simd")]
+mod simd {
+    use core::simd::{cmp::SimdPartialOrd, Mask, MaskElement, Select, Simd, SimdElement};
+    use num_traits::{NumCast, Zero};
+
+    /// Pulse Density Modulator driving `LANES` channels in lockstep using portable SIMD
+    ///
+    /// Holds one setpoint vector and `N` integrator vectors (one per modulator order, see
+    /// [`Pdm`](crate::Pdm)) and computes every channel's next bit in a single pass. The
+    /// data-dependent branch in [`Pdm::update`](crate::Pdm::update) becomes a lane-wise
+    /// [`Mask`] select here, so the recurrence has no per-channel branching and vectorizes.
+    ///
+    /// Only modulator types whose [`Modulator::SigmaType`](crate::Modulator::SigmaType) fits
+    /// in a SIMD lane (`i16`/`i32`/`i64`) are supported — `u64`/`i64` use an `i128` accumulator,
+    /// which `core::simd` has no lane type for.
+    pub struct PdmSimd<T, const LANES: usize, const N: usize = 2>
+    where
+        T: crate::Modulator<SigmaType: SimdElement + MaskElement>
+    {
+        value: Simd<T::SigmaType, LANES>,
+        sigma: [Simd<T::SigmaType, LANES>; N]
+    }
 
-        impl Default for Pdm<$T> {
-            fn default() -> Self {
-                Self {
-                    value: 0.0,
-                    sigma: [0.0; 2]
-                }
+    /// Packs a lane mask's set bits into an unsigned integer (lane 0 in bit 0) for callers
+    /// handing the result to DMA or another block-oriented sink
+    pub fn mask_to_bits<T, const LANES: usize>(mask: Mask<T, LANES>) -> u64
+    where
+        T: MaskElement
+    {
+        let mut bits = 0u64;
+        for lane in 0..LANES {
+            if mask.test(lane) {
+                bits |= 1 << lane;
             }
         }
+        bits
+    }
+
+    impl<T, const LANES: usize, const N: usize> Default for PdmSimd<T, LANES, N>
+    where
+        T: crate::Modulator<SigmaType: SimdElement + MaskElement>,
+        Simd<T::SigmaType, LANES>: core::ops::Add<Output = Simd<T::SigmaType, LANES>>
+            + core::ops::Sub<Output = Simd<T::SigmaType, LANES>>
+            + core::ops::Mul<Output = Simd<T::SigmaType, LANES>>
+            + core::ops::Neg<Output = Simd<T::SigmaType, LANES>>
+            + SimdPartialOrd<Mask = Mask<T::SigmaType, LANES>>
+    {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
 
-        impl Pdm<$T> {
-            /// Initializes a new modulator
-            ///
-            /// Values are expected between -1.0 and 1.0
-            pub fn new() -> Self {
-                Default::default()
+    impl<T, const LANES: usize, const N: usize> PdmSimd<T, LANES, N>
+    where
+        T: crate::Modulator<SigmaType: SimdElement + MaskElement>,
+        Simd<T::SigmaType, LANES>: core::ops::Add<Output = Simd<T::SigmaType, LANES>>
+            + core::ops::Sub<Output = Simd<T::SigmaType, LANES>>
+            + core::ops::Mul<Output = Simd<T::SigmaType, LANES>>
+            + core::ops::Neg<Output = Simd<T::SigmaType, LANES>>
+            + SimdPartialOrd<Mask = Mask<T::SigmaType, LANES>>
+    {
+        /// Initializes a new multi-channel modulator
+        pub fn new() -> Self {
+            let zero = Simd::splat(T::SigmaType::zero());
+            Self {
+                value: zero,
+                sigma: [zero; N]
             }
+        }
 
-            /// Returns the next output value of the modulator
-            pub fn update(&mut self) -> bool {
-                let mut sigma_new: [$T; 2] = [0.0; 2];
-                if self.sigma[1] >= 0.0 {
-                    sigma_new[0] = self.sigma[0] + self.value as $T - 1.0 as $T;
-                    sigma_new[1] = self.sigma[1] + sigma_new[0] - 1.0 as $T;
-                } else {
-                    sigma_new[0] = self.sigma[0] + self.value as $T + 1.0 as $T;
-                    sigma_new[1] = self.sigma[1] + sigma_new[0] + 1.0 as $T;
-                }
-                self.sigma = sigma_new;
-                self.sigma[1] >= 0.0
+        /// Sets the per-channel modulator values
+        pub fn set_value(&mut self, value: [T; LANES]) {
+            self.value = Simd::from_array(value.map(|v| <T::SigmaType as NumCast>::from(v).unwrap()));
+        }
+
+        /// Clamps `value` to `[-bound, bound]` lane-wise
+        ///
+        /// Mirrors [`Pdm`](crate::Pdm)'s own `clamp_sigma` — see its docs for why the bound
+        /// is needed.
+        fn clamp_sigma(
+            value: Simd<T::SigmaType, LANES>,
+            bound: Simd<T::SigmaType, LANES>
+        ) -> Simd<T::SigmaType, LANES> {
+            let over = value.simd_gt(bound);
+            let under = value.simd_lt(-bound);
+            over.select(bound, under.select(-bound, value))
+        }
+
+        /// Returns the next output bits of the modulator, one per channel, as a lane mask
+        pub fn update(&mut self) -> Mask<T::SigmaType, LANES> {
+            let zero = Simd::splat(T::SigmaType::zero());
+            let high = Simd::splat(T::full_scale());
+            let low = if T::is_unsigned() { zero } else { Simd::splat(-T::full_scale()) };
+            let is_high = self.sigma[N - 1].simd_ge(zero);
+            let f = is_high.select(high, low);
+            let bound = high * Simd::splat(<T::SigmaType as NumCast>::from(4 * N).unwrap());
+
+            let mut sigma_new: [Simd<T::SigmaType, LANES>; N] = [zero; N];
+            sigma_new[0] = Self::clamp_sigma(self.sigma[0] + self.value - f, bound);
+            for i in 1..N {
+                sigma_new[i] = Self::clamp_sigma(self.sigma[i] + sigma_new[i - 1] - f, bound);
             }
+            self.sigma = sigma_new;
+            self.sigma[N - 1].simd_ge(zero)
         }
-    };
+    }
 }
 
-gen_float_impl!(f64);
-gen_float_impl!(f32);
-
 /******************************************************************************/
 
 #[cfg(test)]
@@ -239,4 +407,261 @@ mod tests {
     }
     gen_float_test!(test_f32, f32, 0.42, 500_000);
     gen_float_test!(test_f64, f64, -0.42, 500_000);
+
+    macro_rules! gen_order_test {
+        ($name: ident, $T: ty, $N: literal, $setpoint: expr, $iterations: literal) => {
+            #[test]
+            fn $name() {
+                let mut pdm = Pdm::<$T, $N>::new();
+                pdm.set_value($setpoint);
+
+                let mut avg = 0;
+                for _ in 0..$iterations {
+                    if pdm.update() {
+                        avg += <$T>::MAX as u128;
+                    }
+                }
+                let ratio = (avg as f64 / $iterations as f64) / $setpoint as f64;
+                assert!(ratio >= 0.99 && ratio <= 1.01, "ratio: {}", ratio);
+            }
+        };
+    }
+    gen_order_test!(test_u16_order1, u16, 1, 42_000, 500_000);
+    gen_order_test!(test_u16_order3, u16, 3, 42_000, 500_000);
+    gen_order_test!(test_u16_order4, u16, 4, 42_000, 500_000);
+
+    /// Minimal xorshift64* RNG used only to exercise `update_dithered` without an external generator
+    struct XorShiftRng(u64);
+    impl rand_core::RngCore for XorShiftRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for chunk in dest.chunks_mut(8) {
+                chunk.copy_from_slice(&self.next_u64().to_ne_bytes()[..chunk.len()]);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_u16_dithered() {
+        let mut pdm = Pdm::<u16>::new();
+        pdm.set_value(42_000);
+        let mut rng = XorShiftRng(0xDEAD_BEEF_CAFE_F00D);
+
+        let mut avg = 0;
+        for _ in 0..500_000 {
+            if pdm.update_dithered(&mut rng) {
+                avg += u16::MAX as u128;
+            }
+        }
+        let ratio = (avg as f64 / 500_000 as f64) / 42_000 as f64;
+        assert!(ratio >= 0.99 && ratio <= 1.01, "ratio: {}", ratio);
+    }
+
+    #[test]
+    fn test_i16_dithered() {
+        let mut pdm = Pdm::<i16>::new();
+        pdm.set_value(4_200);
+        let mut rng = XorShiftRng(0xDEAD_BEEF_CAFE_F00D);
+
+        let mut avg = 0;
+        for _ in 0..500_000 {
+            if pdm.update_dithered(&mut rng) {
+                avg += i16::MAX as i128;
+            } else {
+                avg -= i16::MAX as i128;
+            }
+        }
+        let ratio = (avg as f64 / 500_000 as f64) / 4_200 as f64;
+        assert!(ratio >= 0.99 && ratio <= 1.01, "ratio: {}", ratio);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_u16_simd() {
+        use crate::simd::{mask_to_bits, PdmSimd};
+
+        let mut pdm = PdmSimd::<u16, 4>::new();
+        pdm.set_value([10_000, 20_000, 30_000, 42_000]);
+
+        let mut avg = [0u128; 4];
+        for _ in 0..500_000 {
+            let bits = mask_to_bits(pdm.update());
+            for lane in 0..4 {
+                if bits & (1 << lane) != 0 {
+                    avg[lane] += u16::MAX as u128;
+                }
+            }
+        }
+
+        let setpoints = [10_000.0, 20_000.0, 30_000.0, 42_000.0];
+        for lane in 0..4 {
+            let ratio = (avg[lane] as f64 / 500_000 as f64) / setpoints[lane];
+            assert!(ratio >= 0.99 && ratio <= 1.01, "lane {} ratio: {}", lane, ratio);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    macro_rules! gen_simd_order_test {
+        ($name: ident, $N: literal) => {
+            #[test]
+            fn $name() {
+                use crate::simd::{mask_to_bits, PdmSimd};
+
+                let setpoints = [10_000u16, 20_000, 30_000, 42_000];
+                let mut pdm = PdmSimd::<u16, 4, $N>::new();
+                pdm.set_value(setpoints);
+
+                let mut avg = [0u128; 4];
+                for _ in 0..500_000 {
+                    let bits = mask_to_bits(pdm.update());
+                    for lane in 0..4 {
+                        if bits & (1 << lane) != 0 {
+                            avg[lane] += u16::MAX as u128;
+                        }
+                    }
+                }
+
+                for lane in 0..4 {
+                    let ratio = (avg[lane] as f64 / 500_000 as f64) / setpoints[lane] as f64;
+                    assert!(ratio >= 0.99 && ratio <= 1.01, "lane {} ratio: {}", lane, ratio);
+                }
+            }
+        };
+    }
+    #[cfg(feature = "simd")]
+    gen_simd_order_test!(test_u16_simd_order3, 3);
+    #[cfg(feature = "simd")]
+    gen_simd_order_test!(test_u16_simd_order4, 4);
+
+    #[test]
+    fn test_update_block_matches_update() {
+        let mut packed = Pdm::<u16>::new();
+        packed.set_value(42_000);
+        let mut scalar = Pdm::<u16>::new();
+        scalar.set_value(42_000);
+
+        for _ in 0..1_000 {
+            let (word, count) = packed.update_block_u8_with_count(BitOrder::MsbFirst);
+            let mut expected = 0u8;
+            let mut expected_count = 0;
+            for i in 0..8 {
+                if scalar.update() {
+                    expected_count += 1;
+                    expected |= 1 << (7 - i);
+                }
+            }
+            assert_eq!(word, expected);
+            assert_eq!(count, expected_count);
+        }
+    }
+
+    #[test]
+    fn test_update_block_bytes_lsb_first() {
+        let mut pdm = Pdm::<u16>::new();
+        pdm.set_value(42_000);
+        let mut reference = Pdm::<u16>::new();
+        reference.set_value(42_000);
+
+        let mut buf = [0u8; 4];
+        let count = pdm.update_block_bytes(&mut buf, BitOrder::LsbFirst);
+
+        let mut expected_count = 0;
+        for byte in buf.iter() {
+            for i in 0..8 {
+                if reference.update() {
+                    expected_count += 1;
+                    assert_ne!(byte & (1 << i), 0);
+                } else {
+                    assert_eq!(byte & (1 << i), 0);
+                }
+            }
+        }
+        assert_eq!(count, expected_count);
+    }
+
+    /// A toy 0-100 "percent" type, standing in for a third-party fixed-point/wrapper type that
+    /// only implements the `num-traits` bounds `Modulator` requires
+    #[derive(Clone, Copy, PartialEq, PartialOrd)]
+    struct Percent(i8);
+
+    impl core::ops::Add for Percent {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Percent(self.0 + rhs.0)
+        }
+    }
+
+    impl num_traits::Zero for Percent {
+        fn zero() -> Self {
+            Percent(0)
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    impl num_traits::Bounded for Percent {
+        fn min_value() -> Self {
+            Percent(0)
+        }
+
+        fn max_value() -> Self {
+            Percent(100)
+        }
+    }
+
+    impl num_traits::ToPrimitive for Percent {
+        fn to_i64(&self) -> Option<i64> {
+            Some(self.0 as i64)
+        }
+
+        fn to_u64(&self) -> Option<u64> {
+            Some(self.0 as u64)
+        }
+    }
+
+    impl num_traits::NumCast for Percent {
+        fn from<N: num_traits::ToPrimitive>(n: N) -> Option<Self> {
+            n.to_i64().map(|v| Percent(v as i8))
+        }
+    }
+
+    impl Modulator for Percent {
+        type SigmaType = i32;
+
+        fn full_scale() -> i32 {
+            100
+        }
+    }
+
+    #[test]
+    fn test_custom_modulator_type() {
+        let mut pdm = Pdm::<Percent>::new();
+        pdm.set_value(Percent(75));
+
+        let mut avg = 0u128;
+        for _ in 0..200_000 {
+            if pdm.update() {
+                avg += 1;
+            }
+        }
+        let ratio = (avg as f64 / 200_000 as f64) / 0.75;
+        assert!(ratio >= 0.99 && ratio <= 1.01, "ratio: {}", ratio);
+    }
 }